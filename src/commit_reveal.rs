@@ -0,0 +1,54 @@
+use ethers::utils::keccak256;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+// Tracks the commit-reveal state for one chain's draw sequence: the secret
+// seed, its on-chain commitment, and how far into the sequence we are. This
+// is what lets any client recompute and audit every drawn number once the
+// seed is revealed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeedState {
+    pub seed: [u8; 32],
+    pub commitment: [u8; 32],
+    pub draw_index: u64,
+    pub committed: bool,
+    pub revealed: bool,
+}
+
+impl SeedState {
+    pub fn generate() -> Self {
+        let seed: [u8; 32] = rand::random();
+        let commitment = keccak256(seed);
+        Self {
+            seed,
+            commitment,
+            draw_index: 0,
+            committed: false,
+            revealed: false,
+        }
+    }
+}
+
+// The drawn number for `draw_index` is `(keccak256(seed || draw_index)[0] % 99) + 1`,
+// deterministic and reproducible by anyone who has the revealed seed.
+pub fn derive_number(seed: &[u8; 32], draw_index: u64) -> u8 {
+    let mut preimage = Vec::with_capacity(40);
+    preimage.extend_from_slice(seed);
+    preimage.extend_from_slice(&draw_index.to_be_bytes());
+    let hash = keccak256(preimage);
+    (hash[0] % 99) + 1
+}
+
+pub fn load(path: &str) -> Option<SeedState> {
+    let bytes = fs::read(path).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+pub fn save(path: &str, state: &SeedState) -> std::io::Result<()> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let bytes = serde_json::to_vec_pretty(state)?;
+    fs::write(path, bytes)
+}
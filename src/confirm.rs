@@ -0,0 +1,62 @@
+use ethers::providers::Middleware;
+use ethers::types::TransactionReceipt;
+use serde::Serialize;
+use tokio::time::Duration;
+
+// How often to poll for block height while waiting for a transaction to
+// reach its required confirmation depth.
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Confirmation {
+    pub confirmations: u64,
+    // False if the transaction's block was orphaned by a reorg and the
+    // receipt could no longer be found at its original block hash.
+    pub canonical: bool,
+    // True if the receipt confirmed at its required depth but the
+    // transaction itself reverted on execution. `canonical` alone can't
+    // distinguish this from a genuine success, since a reverted tx still
+    // gets a stable receipt at a stable block hash.
+    pub reverted: bool,
+}
+
+// Polls until `receipt` is at least `confirmations` blocks deep, then
+// re-fetches it once more to check a reorg hasn't since orphaned it. If the
+// chain reorganized onto a different block containing the same tx, keeps
+// polling from the new position; if the tx disappeared entirely, returns
+// `canonical: false` so the caller can resubmit.
+pub async fn wait_for_confirmations<M: Middleware>(
+    client: &M,
+    mut receipt: TransactionReceipt,
+    confirmations: u64,
+) -> Result<(TransactionReceipt, Confirmation), M::Error> {
+    loop {
+        let current_block = client.get_block_number().await?.as_u64();
+        let receipt_block = receipt.block_number.map(|b| b.as_u64()).unwrap_or(current_block);
+        let depth = current_block.saturating_sub(receipt_block);
+
+        if depth >= confirmations {
+            match client.get_transaction_receipt(receipt.transaction_hash).await? {
+                Some(latest) if latest.block_hash == receipt.block_hash => {
+                    let reverted = latest.status == Some(0.into());
+                    return Ok((
+                        latest,
+                        Confirmation { confirmations: depth, canonical: true, reverted },
+                    ));
+                }
+                Some(latest) => {
+                    receipt = latest;
+                    continue;
+                }
+                None => {
+                    return Ok((
+                        receipt,
+                        Confirmation { confirmations: depth, canonical: false, reverted: false },
+                    ));
+                }
+            }
+        }
+
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
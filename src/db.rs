@@ -0,0 +1,182 @@
+use eyre::Result;
+use serde::Serialize;
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct DrawRecord {
+    pub draw_index: i64,
+    pub number: i64,
+    pub tx_hash: String,
+    pub block_number: i64,
+    pub submitted_at: i64,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct CardRecord {
+    pub wallet_address: String,
+    pub card_numbers: String,
+    pub tx_hash: String,
+    pub purchased_at: i64,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ClaimRecord {
+    pub wallet_address: String,
+    pub tx_hash: String,
+    pub claimed_at: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChainHistory {
+    pub draws: Vec<DrawRecord>,
+    pub cards: Vec<CardRecord>,
+    pub claims: Vec<ClaimRecord>,
+}
+
+// Per-chain SQLite-backed record of draws, purchased cards, and win claims, so
+// a restart doesn't lose game history and the background submitter can pick
+// up where it left off.
+#[derive(Debug, Clone)]
+pub struct Store {
+    pool: SqlitePool,
+}
+
+impl Store {
+    pub async fn connect(chain_name: &str) -> Result<Self> {
+        let path = format!("data/{}.db", chain_name);
+        if let Some(parent) = Path::new(&path).parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let pool = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect(&format!("sqlite://{}?mode=rwc", path))
+            .await?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS draws (
+                draw_index INTEGER PRIMARY KEY,
+                number INTEGER NOT NULL,
+                tx_hash TEXT NOT NULL,
+                block_number INTEGER NOT NULL,
+                submitted_at INTEGER NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await?;
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS cards (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                wallet_address TEXT NOT NULL,
+                card_numbers TEXT NOT NULL,
+                tx_hash TEXT NOT NULL,
+                purchased_at INTEGER NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await?;
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS claims (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                wallet_address TEXT NOT NULL,
+                tx_hash TEXT NOT NULL,
+                claimed_at INTEGER NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await?;
+
+        Ok(Self { pool })
+    }
+
+    pub async fn record_draw(
+        &self,
+        draw_index: u64,
+        number: u8,
+        tx_hash: &str,
+        block_number: u64,
+        submitted_at: i64,
+    ) -> sqlx::Result<()> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO draws (draw_index, number, tx_hash, block_number, submitted_at)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(draw_index as i64)
+        .bind(number as i64)
+        .bind(tx_hash)
+        .bind(block_number as i64)
+        .bind(submitted_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn record_card(
+        &self,
+        wallet_address: &str,
+        card_numbers: &[u32; 25],
+        tx_hash: &str,
+        purchased_at: i64,
+    ) -> sqlx::Result<()> {
+        let card_numbers = serde_json::to_string(card_numbers)
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+        sqlx::query(
+            "INSERT INTO cards (wallet_address, card_numbers, tx_hash, purchased_at)
+             VALUES (?, ?, ?, ?)",
+        )
+        .bind(wallet_address)
+        .bind(card_numbers)
+        .bind(tx_hash)
+        .bind(purchased_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn record_claim(&self, wallet_address: &str, tx_hash: &str, claimed_at: i64) -> sqlx::Result<()> {
+        sqlx::query("INSERT INTO claims (wallet_address, tx_hash, claimed_at) VALUES (?, ?, ?)")
+            .bind(wallet_address)
+            .bind(tx_hash)
+            .bind(claimed_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    // The draw index one past the highest one recorded, i.e. where the
+    // submitter should resume after a restart.
+    pub async fn next_draw_index(&self) -> sqlx::Result<u64> {
+        let row: (Option<i64>,) = sqlx::query_as("SELECT MAX(draw_index) FROM draws")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.0.map(|max| max as u64 + 1).unwrap_or(0))
+    }
+
+    pub async fn history(&self) -> sqlx::Result<ChainHistory> {
+        let draws = sqlx::query_as::<_, DrawRecord>(
+            "SELECT draw_index, number, tx_hash, block_number, submitted_at FROM draws ORDER BY draw_index ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let cards = sqlx::query_as::<_, CardRecord>(
+            "SELECT wallet_address, card_numbers, tx_hash, purchased_at FROM cards ORDER BY purchased_at ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let claims = sqlx::query_as::<_, ClaimRecord>(
+            "SELECT wallet_address, tx_hash, claimed_at FROM claims ORDER BY claimed_at ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ChainHistory { draws, cards, claims })
+    }
+}
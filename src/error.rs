@@ -0,0 +1,71 @@
+use ethers::contract::ContractError;
+use ethers::providers::ProviderError;
+use rocket::http::Status;
+use rocket::response::{self, Responder};
+use rocket::serde::json::Json;
+use rocket::Request;
+use thiserror::Error;
+
+use crate::{ApiResponse, ChainSigner};
+
+// Every handler failure funnels through here so the frontend always gets the
+// right HTTP status alongside a uniform JSON body, instead of a mix of bare
+// status codes and HTTP-200-with-`success:false` responses.
+#[derive(Debug, Error)]
+pub enum BingoError {
+    #[error("chain '{0}' is not registered")]
+    ChainNotFound(String),
+
+    #[error("invalid wallet address: {0}")]
+    InvalidAddress(String),
+
+    #[error("contract call failed: {0}")]
+    ContractCall(#[from] ContractError<ChainSigner>),
+
+    #[error("provider error: {0}")]
+    Provider(#[from] ProviderError),
+
+    #[error("transaction did not confirm")]
+    TxFailed,
+
+    #[error("game has already started")]
+    GameAlreadyStarted,
+
+    #[error("failed to initialize chain: {0}")]
+    ChainInit(String),
+
+    #[error("missing or invalid admin API key")]
+    Unauthorized,
+
+    #[error("failed to persist state: {0}")]
+    Persistence(#[from] std::io::Error),
+
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl<'r> Responder<'r, 'static> for BingoError {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let status = match &self {
+            BingoError::ChainNotFound(_) => Status::NotFound,
+            BingoError::InvalidAddress(_) | BingoError::ChainInit(_) => Status::BadRequest,
+            BingoError::GameAlreadyStarted => Status::Conflict,
+            BingoError::Unauthorized => Status::Unauthorized,
+            BingoError::ContractCall(_) | BingoError::Provider(_) => Status::BadGateway,
+            BingoError::TxFailed | BingoError::Persistence(_) | BingoError::Database(_) => {
+                Status::InternalServerError
+            }
+        };
+
+        let body = ApiResponse::<()> {
+            success: false,
+            message: self.to_string(),
+            data: None,
+        };
+
+        Json(body).respond_to(request).map(|mut res| {
+            res.set_status(status);
+            res
+        })
+    }
+}
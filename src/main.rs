@@ -2,131 +2,189 @@ use dotenv::dotenv;
 use ethers::types::Address;
 use ethers::{
     prelude::*,
-    providers::{Http, Provider},
+    providers::{Http, Provider, Ws},
     signers::{LocalWallet, Signer},
 };
 use eyre::Result;
+use futures::stream::StreamExt;
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
-use rocket::fairing::{Fairing, Info, Kind};
 use rocket::http::Method;
 use rocket::http::{Header, Status};
+use rocket::outcome::Outcome;
+use rocket::request::{self, FromRequest};
+use rocket::response::stream::{Event, EventStream};
 use rocket::Request;
-use rocket::{get, launch, post, response::Response, routes, serde::json::Json, State};
+use rocket::{
+    catchers, delete, get, launch, post, response::Response, routes, serde::json::Json, State,
+};
 use rocket_cors::{AllowedOrigins, CorsOptions};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::{str::FromStr, sync::Arc};
-use tokio::sync::Mutex;
+use tokio::sync::{broadcast, Mutex};
 use tokio::time::{sleep, Duration};
-
-// Background task control structure
-pub struct BackgroundSubmitter {
-    is_running: Arc<AtomicBool>,
-}
-
-impl BackgroundSubmitter {
-    pub fn new() -> Self {
-        Self {
-            is_running: Arc::new(AtomicBool::new(true)),
+use tokio_stream::wrappers::BroadcastStream;
+
+mod commit_reveal;
+mod confirm;
+mod db;
+mod error;
+mod tx_manager;
+use commit_reveal::SeedState;
+use error::BingoError;
+use tx_manager::TxManager;
+
+// Shorthand for the signer middleware every chain's writable contract handle
+// is built on; shared with `error` and `tx_manager` so they don't each pin
+// their own copy of this type.
+pub type ChainSigner = SignerMiddleware<Provider<Http>, LocalWallet>;
+
+// How often the watchdog checks for stuck transactions, and how long a
+// transaction may sit unconfirmed before it gets resent with bumped fees.
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(10);
+const TX_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Confirmation depth used when a chain doesn't set one explicitly via
+// CONFIRMATIONS. 1 just means "included in a block", i.e. today's behavior.
+const DEFAULT_CONFIRMATIONS: u64 = 1;
+
+// Spawns the three background tasks (number submission loop, tx watchdog,
+// and — if a websocket endpoint was configured — event subscription) for a
+// single chain, gated on that chain's own `task_control` flag. Called by
+// `register_chain` right after a chain is inserted into the map, whether
+// that happens at startup or via the admin registration endpoint.
+fn spawn_chain_tasks(chain_id: String, state: &ChainState) {
+    let contract = state.app_state.contract.clone();
+    let tx_manager = state.tx_manager.clone();
+    let seed_state = state.seed_state.clone();
+    let seed_state_path = state.seed_state_path.clone();
+    let is_running = state.task_control.clone();
+
+    tokio::spawn({
+        let chain_id = chain_id.clone();
+        async move {
+            println!(
+                "Starting background number submission task for chain {}...",
+                chain_id
+            );
+            while is_running.load(Ordering::SeqCst) {
+                match submit_number(&contract, &tx_manager, &seed_state, &seed_state_path).await {
+                    Ok(_) => println!("Successfully submitted number for chain {}", chain_id),
+                    Err(e) => eprintln!("Error submitting number for chain {}: {}", chain_id, e),
+                }
+                sleep(Duration::from_secs(15)).await;
+            }
+            println!("Background task stopped for chain {}", chain_id);
         }
-    }
-
-    pub fn get_fairing(&self) -> BackgroundFairing {
-        BackgroundFairing {
-            is_running: self.is_running.clone(),
+    });
+
+    let watchdog_contract = state.app_state.contract.clone();
+    let watchdog_tx_manager = state.tx_manager.clone();
+    let watchdog_running = state.task_control.clone();
+    tokio::spawn({
+        let chain_id = chain_id.clone();
+        async move {
+            println!("Starting tx watchdog for chain {}...", chain_id);
+            while watchdog_running.load(Ordering::SeqCst) {
+                sleep(WATCHDOG_INTERVAL).await;
+                watchdog_tx_manager
+                    .watchdog_tick(&watchdog_contract, TX_TIMEOUT)
+                    .await;
+            }
         }
-    }
-
-    pub fn stop(&self) {
-        self.is_running.store(false, Ordering::SeqCst);
+    });
+
+    if let Some(ws_contract) = state.ws_contract.clone() {
+        let events_tx = state.events_tx.clone();
+        let is_running = state.task_control.clone();
+        tokio::spawn({
+            let chain_id = chain_id.clone();
+            async move {
+                println!("Starting event subscription task for chain {}...", chain_id);
+                if let Err(e) = subscribe_to_events(&ws_contract, &events_tx, &is_running).await {
+                    eprintln!("Event subscription for chain {} ended: {}", chain_id, e);
+                }
+            }
+        });
     }
 }
-#[rocket::async_trait]
-impl Fairing for BackgroundFairing {
-    fn info(&self) -> Info {
-        Info {
-            name: "Background Number Submitter",
-            kind: Kind::Ignite,
-        }
-    }
 
-    async fn on_ignite(
-        &self,
-        rocket: rocket::Rocket<rocket::Build>,
-    ) -> Result<rocket::Rocket<rocket::Build>, rocket::Rocket<rocket::Build>> {
-        let chain_states = rocket
-            .state::<Arc<Mutex<HashMap<String, ChainState>>>>()
-            .unwrap()
-            .clone();
-        let chain_states = chain_states.lock().await;
-        for (chain_id, state) in chain_states.iter() {
-            let app_state: AppState = state.app_state.clone();
-            let contract = app_state.contract.clone();
-            let is_running = self.is_running.clone();
-
-            tokio::spawn({
-                let chain_id = chain_id.clone();
-                async move {
-                    println!(
-                        "Starting background number submission task for chain {}...",
-                        chain_id
-                    );
-                    while is_running.load(Ordering::SeqCst) {
-                        match submit_number(&contract).await {
-                            Ok(_) => {
-                                println!("Successfully submitted number for chain {}", chain_id)
-                            }
-                            Err(e) => {
-                                eprintln!("Error submitting number for chain {}: {}", chain_id, e)
-                            }
-                        }
-                        sleep(Duration::from_secs(15)).await;
-                    }
-                    println!("Background task stopped for chain {}", chain_id);
-                }
-            });
+// Streams `NumberDrawn`/`GameStarted`/`WinClaim` logs from the contract over the
+// websocket provider and fans them out to subscribed SSE clients.
+async fn subscribe_to_events(
+    contract: &Arc<BingoGame<Provider<Ws>>>,
+    events_tx: &broadcast::Sender<GameEvent>,
+    is_running: &Arc<AtomicBool>,
+) -> Result<()> {
+    let events = contract.events();
+    let mut stream = events.subscribe().await?;
+
+    while is_running.load(Ordering::SeqCst) {
+        match stream.next().await {
+            Some(Ok(BingoGameEvents::GameStartedFilter(event))) => {
+                let _ = events_tx.send(GameEvent::GameStarted {
+                    start_time: event.start_time.as_u64(),
+                });
+            }
+            Some(Ok(BingoGameEvents::NumberDrawnFilter(event))) => {
+                let _ = events_tx.send(GameEvent::NumberDrawn {
+                    number: event.number.as_u32() as u8,
+                    draw_index: event.draw_index.as_u64(),
+                });
+            }
+            Some(Ok(BingoGameEvents::WinClaimFilter(event))) => {
+                let _ = events_tx.send(GameEvent::WinClaimed {
+                    player: format!("{:?}", event.player),
+                });
+            }
+            Some(Err(e)) => eprintln!("Error decoding contract log: {}", e),
+            None => break,
         }
-        Ok(rocket)
     }
+    Ok(())
 }
 
 async fn submit_number(
-    contract: &Arc<BingoGame<SignerMiddleware<Provider<Http>, LocalWallet>>>,
+    contract: &Arc<BingoGame<ChainSigner>>,
+    tx_manager: &Arc<TxManager>,
+    seed_state: &Arc<Mutex<SeedState>>,
+    seed_state_path: &str,
 ) -> Result<()> {
-    let is_game_started = contract.is_game_started().call().await?;
-    if is_game_started {
-        // Generate random number between 1 and 99
-        let mut rng = StdRng::from_entropy();
-        let random_number = rng.gen::<u8>();
-        let number = (random_number % 99) + 1;
-        println!("Submitting number: {}", number);
-
-        // Submit transaction
-        let number_u256 = U256::from(number);
-        let submit_call = contract.submit_drawn_number(number_u256);
-        let tx = submit_call.send().await?;
-
-        // Wait for confirmation
-        let receipt = tx.await?;
-        if let Some(receipt) = receipt {
-            println!(
-                "Number {} submitted in block: {:?}",
-                number, receipt.block_number
-            );
-        } else {
-            println!("Number {} submitted but receipt is None", number);
+    let (_, _, _, _, is_ended, _, is_started) = contract.get_current_game_state().call().await?;
+
+    if is_ended {
+        let mut state = seed_state.lock().await;
+        if !state.revealed {
+            contract.reveal_seed(state.seed).send().await?.await?;
+            state.revealed = true;
+            commit_reveal::save(seed_state_path, &state)?;
+            println!("Revealed draw seed after game end");
         }
+        return Ok(());
     }
 
-    Ok(())
-}
+    if is_started {
+        let mut state = seed_state.lock().await;
+        let draw_index = state.draw_index;
+        // Derived deterministically from the committed seed, so the operator
+        // can't bias which number comes up next.
+        let number = commit_reveal::derive_number(&state.seed, draw_index);
+        println!("Submitting number: {} (draw #{})", number, draw_index);
+
+        // Hand off to the tx manager, which assigns an explicit nonce and tracks
+        // the transaction for the watchdog; it does not wait for confirmation so
+        // the next draw isn't blocked on this one.
+        tx_manager
+            .submit_drawn_number(contract, number, draw_index)
+            .await?;
+
+        state.draw_index += 1;
+        commit_reveal::save(seed_state_path, &state)?;
+    }
 
-// Rocket Fairing for background task
-#[derive(Debug)]
-pub struct BackgroundFairing {
-    is_running: Arc<AtomicBool>,
+    Ok(())
 }
 
 // Define a struct to hold the state for each chain
@@ -136,40 +194,178 @@ struct ChainState {
     contract_address: String,
     private_key: String,
     app_state: AppState,
+    // Read-only contract handle over the websocket provider, used to subscribe to
+    // contract logs. `None` when no WS_URLS entry was configured for this chain.
+    ws_contract: Option<Arc<BingoGame<Provider<Ws>>>>,
+    events_tx: broadcast::Sender<GameEvent>,
+    tx_manager: Arc<TxManager>,
+    seed_state: Arc<Mutex<SeedState>>,
+    seed_state_path: String,
+    db: Arc<db::Store>,
+    // How many blocks must bury a submitted/claim tx before it's reported as
+    // final rather than provisional; guards against shallow reorgs.
+    confirmations: u64,
+    // Independently gates this chain's background tasks so removing one
+    // chain via the admin API can't affect any other chain's tasks.
+    task_control: Arc<AtomicBool>,
 }
 
 impl ChainState {
     async fn new(
+        chain_name: &str,
         rpc_url: &str,
         contract_address: &str,
         private_key: &str,
+        ws_url: Option<&str>,
+        confirmations: u64,
     ) -> Result<Self, Box<dyn std::error::Error>> {
         let app_state = AppState::new(rpc_url, contract_address, private_key).await?;
+
+        let ws_contract = if let Some(ws_url) = ws_url {
+            let provider = Provider::<Ws>::connect(ws_url).await?;
+            let address = Address::from_str(contract_address)?;
+            Some(Arc::new(BingoGame::new(address, Arc::new(provider))))
+        } else {
+            None
+        };
+
+        let (events_tx, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+
+        let db = Arc::new(db::Store::connect(chain_name).await?);
+        let tx_manager = Arc::new(TxManager::new(&app_state.contract, db.clone()).await?);
+
+        let seed_state_path = format!("data/{}_seed.json", chain_name);
+        let mut seed_state =
+            commit_reveal::load(&seed_state_path).unwrap_or_else(SeedState::generate);
+        if !seed_state.committed {
+            app_state
+                .contract
+                .commit_seed(seed_state.commitment)
+                .send()
+                .await?
+                .await?;
+            seed_state.committed = true;
+            commit_reveal::save(&seed_state_path, &seed_state)?;
+            println!("Committed draw seed on-chain for chain {}", chain_name);
+        }
+        // The recorded draw history is the authoritative resume point: it
+        // survives even if the seed file was lost or edited by hand.
+        let resume_draw_index = db.next_draw_index().await?;
+        if resume_draw_index > seed_state.draw_index {
+            seed_state.draw_index = resume_draw_index;
+            commit_reveal::save(&seed_state_path, &seed_state)?;
+        }
+
         Ok(Self {
             rpc_url: rpc_url.to_string(),
             contract_address: contract_address.to_string(),
             private_key: private_key.to_string(),
             app_state,
+            ws_contract,
+            events_tx,
+            tx_manager,
+            seed_state: Arc::new(Mutex::new(seed_state)),
+            seed_state_path,
+            db,
+            confirmations,
+            task_control: Arc::new(AtomicBool::new(true)),
         })
     }
 }
 
+// A single chain's worth of configuration, whether it came from the
+// comma-separated env vars at startup or the admin registration endpoint.
+// Replaces threading five parallel `Vec<&str>`s through the launch function.
+#[derive(Debug, Clone, Deserialize)]
+struct ChainConfig {
+    chain_name: String,
+    rpc_url: String,
+    contract_address: String,
+    private_key: String,
+    ws_url: Option<String>,
+    confirmations: Option<u64>,
+}
+
+// Builds the `ChainState` for `config`, registers it in `chain_states`, and
+// spawns its background tasks — used both to populate the map at startup and
+// by the `POST /api/admin/chains` endpoint, so a chain added at runtime comes
+// up identically to one configured at launch.
+//
+// The lock is held across the whole build, not just the final insert:
+// `ChainState::new` submits a live on-chain `commitSeed` tx and writes the
+// chain's seed file, so two concurrent registrations of the same chain name
+// must not both reach it — deduplicating only the map insertion still lets
+// both commit a seed on-chain and race to overwrite the same seed file.
+async fn register_chain(
+    chain_states: &Arc<Mutex<HashMap<String, ChainState>>>,
+    config: ChainConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let chain_name = config.chain_name.clone();
+    let mut states = chain_states.lock().await;
+    if states.contains_key(&chain_name) {
+        return Err(format!("chain '{}' is already registered", chain_name).into());
+    }
+
+    let state = ChainState::new(
+        &config.chain_name,
+        &config.rpc_url,
+        &config.contract_address,
+        &config.private_key,
+        config.ws_url.as_deref(),
+        config.confirmations.unwrap_or(DEFAULT_CONFIRMATIONS),
+    )
+    .await?;
+
+    spawn_chain_tasks(chain_name.clone(), &state);
+    states.insert(chain_name, state);
+    Ok(())
+}
+
 // Contract ABI definition
 abigen!(
     BingoGame,
     r#"[
-        function submitDrawnNumber(uint256 number) external
+        function submitDrawnNumber(uint256 number, uint256 drawIndex) external
         function assignCard(address player, uint256 randomSeed) external returns (uint32[25])
         function getCurrentGameState() external view returns (uint256 startTime, uint256 lastDrawTime, uint256 numberCount, uint256[] drawnNumbers, bool isEnded, uint256 playerCount, bool isStarted)
         function getPlayerCards(address player) external view returns (uint32[25] storedNumbers)
         function isGameStarted() external view returns (bool)
         function claimWin(address player) external returns (bool)
+        function commitSeed(bytes32 commitment) external
+        function revealSeed(bytes32 seed) external
+        event GameStarted(uint256 startTime)
+        event NumberDrawn(uint256 number, uint256 drawIndex)
+        event WinClaim(address indexed player)
     ]"#
 );
 
+// A decoded contract log, broadcast to every subscriber of a chain's SSE feed.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum GameEvent {
+    GameStarted { start_time: u64 },
+    NumberDrawn { number: u8, draw_index: u64 },
+    WinClaimed { player: String },
+}
+
+// Bounds how many events a slow SSE client can fall behind before it starts
+// missing them; the broadcast channel drops the oldest entry past this depth.
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
 #[derive(Debug, Serialize)]
 struct BingoCard {
     transaction_hash: String,
+    // How many blocks deep the confirmed receipt is, and whether it's still
+    // canonical; lets the frontend tell a final result from a provisional one.
+    confirmations: u64,
+    canonical: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ClaimResult {
+    won: bool,
+    confirmations: u64,
+    canonical: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -181,6 +377,12 @@ struct GameState {
     is_ended: bool,
     player_count: i32,
     is_started: bool,
+    // keccak256(seed) committed on-chain before the first draw, so anyone can
+    // later verify the revealed seed matches what was promised up front.
+    seed_commitment: String,
+    // Only present once the game has ended and the backend has revealed the
+    // seed on-chain; lets any client recompute every draw and audit it.
+    revealed_seed: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -193,7 +395,7 @@ struct ApiResponse<T> {
 
 #[derive(Debug, Clone)]
 struct AppState {
-    contract: Arc<BingoGame<SignerMiddleware<Provider<Http>, LocalWallet>>>,
+    contract: Arc<BingoGame<ChainSigner>>,
 }
 
 impl AppState {
@@ -222,50 +424,35 @@ struct PurchaseCardRequest {
 async fn get_game_state(
     chain_name: &str,
     chain_states: &State<Arc<Mutex<HashMap<String, ChainState>>>>,
-) -> Result<Json<ApiResponse<GameState>>, Status> {
+) -> Result<Json<ApiResponse<GameState>>, BingoError> {
     let chain_states = chain_states.lock().await;
-    println!("Chain states: {:?}", chain_states);
-    let state = chain_states.get(chain_name).ok_or(Status::NotFound)?;
-    print!("State: {:?}", state);
+    let state = chain_states
+        .get(chain_name)
+        .ok_or_else(|| BingoError::ChainNotFound(chain_name.to_string()))?;
+
+    let (start_time, last_draw_time, number_count, drawn_numbers, is_ended, player_count, is_started) =
+        state.app_state.contract.get_current_game_state().call().await?;
+
+    let seed_state = state.seed_state.lock().await;
+    let game_state = GameState {
+        start_time: start_time.as_u64(),
+        last_draw_time: last_draw_time.as_u64(),
+        drawn_numbers_count: number_count.as_u32() as i8,
+        drawn_numbers: drawn_numbers.iter().map(|n| n.as_u32() as i8).collect(),
+        is_ended,
+        player_count: player_count.as_u32() as i32,
+        is_started,
+        seed_commitment: format!("0x{}", ethers::utils::hex::encode(seed_state.commitment)),
+        revealed_seed: seed_state
+            .revealed
+            .then(|| format!("0x{}", ethers::utils::hex::encode(seed_state.seed))),
+    };
 
-    match state
-        .app_state
-        .contract
-        .get_current_game_state()
-        .call()
-        .await
-    {
-        Ok((
-            start_time,
-            last_draw_time,
-            number_count,
-            drawn_numbers,
-            is_ended,
-            player_count,
-            is_started,
-        )) => {
-            let game_state = GameState {
-                start_time: start_time.as_u64(),
-                last_draw_time: last_draw_time.as_u64(),
-                drawn_numbers_count: number_count.as_u32() as i8,
-                drawn_numbers: drawn_numbers.iter().map(|n| n.as_u32() as i8).collect(),
-                is_ended,
-                player_count: player_count.as_u32() as i32,
-                is_started,
-            };
-
-            Ok(Json(ApiResponse {
-                success: true,
-                message: format_game_status_message(&game_state),
-                data: Some(game_state),
-            }))
-        }
-        Err(e) => Ok(Json(ApiResponse {
-            success: false,
-            message: format!("Failed to get game state: {}", e),
-            data: None,
-        })),
-    }
+    Ok(Json(ApiResponse {
+        success: true,
+        message: format_game_status_message(&game_state),
+        data: Some(game_state),
+    }))
 }
 
 #[post("/card/purchase/<chain_name>", format = "json", data = "<request>")]
@@ -273,82 +460,79 @@ async fn purchase_card(
     chain_name: String,
     request: Json<PurchaseCardRequest>,
     chain_states: &State<Arc<Mutex<HashMap<String, ChainState>>>>,
-) -> Result<Json<ApiResponse<BingoCard>>, Status> {
+) -> Result<Json<ApiResponse<BingoCard>>, BingoError> {
     let chain_states = chain_states.lock().await;
-    println!("Chain states: {:?}", chain_states);
-    let state = chain_states.get(&chain_name).ok_or(Status::NotFound)?;
-    print!("State: {:?}", state);
-    match state
+    let state = chain_states
+        .get(&chain_name)
+        .ok_or_else(|| BingoError::ChainNotFound(chain_name.clone()))?;
+
+    let (_, _, _, _, _, _, is_started) =
+        state.app_state.contract.get_current_game_state().call().await?;
+    if is_started {
+        return Err(BingoError::GameAlreadyStarted);
+    }
+
+    let wallet_address = request
+        .walletAddress
+        .parse::<Address>()
+        .map_err(|_| BingoError::InvalidAddress(request.walletAddress.clone()))?;
+
+    let mut rng = StdRng::from_entropy();
+    let random_number: U256 = U256::from(rng.gen::<u64>());
+
+    let call = state
         .app_state
         .contract
-        .get_current_game_state()
-        .call()
-        .await
-    {
-        Ok((_, _, _, _, _, _, is_started)) => {
-            if is_started {
-                return Ok(Json(ApiResponse {
-                    success: false,
-                    message: "Game has already started".to_string(),
-                    data: None,
-                }));
-            }
-            let mut rng = StdRng::from_entropy();
-            let random_number: U256 = U256::from(rng.gen::<u64>());
-
-            println!("random numbers: {:?}", random_number);
-
-            // Parse the wallet address from the request
-            let parsed_address = request.walletAddress.parse::<Address>();
-            let wallet_address = match &parsed_address {
-                Ok(address) => address,
-                Err(_) => {
-                    eprintln!("Failed to parse wallet address: {}", request.walletAddress);
-                    return Err(Status::BadRequest);
-                }
-            };
-
-            // Assign the card to the given address
-            match state
-                .app_state
-                .contract
-                .assign_card(*wallet_address, random_number)
-                .send()
-                .await
-            {
-                Ok(tx) => match tx.await {
-                    Ok(receipt) => {
-                        let receipt = receipt.ok_or_else(|| {
-                            eprintln!("Transaction receipt is None");
-                            Status::InternalServerError
-                        })?;
-                        Ok(Json(ApiResponse {
-                            success: true,
-                            message: "Bingo card purchased and assigned successfully".to_string(),
-                            data: Some(BingoCard {
-                                transaction_hash: format!("{:?}", receipt.transaction_hash),
-                            }),
-                        }))
-                    }
-                    Err(e) => Ok(Json(ApiResponse {
-                        success: false,
-                        message: format!("Transaction failed: {}", e),
-                        data: None,
-                    })),
-                },
-                Err(e) => Ok(Json(ApiResponse {
-                    success: false,
-                    message: format!("Failed to send transaction: {}", e),
-                    data: None,
-                })),
-            }
-        }
-        Err(e) => Ok(Json(ApiResponse {
-            success: false,
-            message: format!("Failed to check game state: {}", e),
-            data: None,
-        })),
+        .assign_card(wallet_address, random_number);
+    let tx = call.send().await?;
+    let receipt = tx.await?.ok_or(BingoError::TxFailed)?;
+
+    let (receipt, confirmation) = confirm::wait_for_confirmations(
+        state.app_state.contract.client().inner(),
+        receipt,
+        state.confirmations,
+    )
+    .await?;
+    // The original assignment vanished in a reorg; resend once with a fresh
+    // random number rather than report a card that no longer exists on-chain.
+    let (receipt, confirmation) = if confirmation.canonical {
+        (receipt, confirmation)
+    } else {
+        let random_number: U256 = U256::from(rng.gen::<u64>());
+        let call = state
+            .app_state
+            .contract
+            .assign_card(wallet_address, random_number);
+        let tx = call.send().await?;
+        let receipt = tx.await?.ok_or(BingoError::TxFailed)?;
+        confirm::wait_for_confirmations(state.app_state.contract.client().inner(), receipt, state.confirmations)
+            .await?
+    };
+    if confirmation.reverted {
+        return Err(BingoError::TxFailed);
     }
+    let tx_hash = format!("{:?}", receipt.transaction_hash);
+
+    let card_numbers = state
+        .app_state
+        .contract
+        .get_player_cards(wallet_address)
+        .call()
+        .await?;
+    state
+        .db
+        .record_card(&request.walletAddress, &card_numbers, &tx_hash, db::now_unix())
+        .await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        message: "Bingo card purchased and assigned successfully".to_string(),
+        data: Some(BingoCard {
+            transaction_hash: tx_hash,
+            confirmations: confirmation.confirmations,
+            canonical: confirmation.canonical,
+        }),
+    }))
 }
 
 #[post("/card/get/<chain_name>", format = "json", data = "<request>")]
@@ -356,42 +540,29 @@ async fn get_card(
     chain_name: String,
     request: Json<PurchaseCardRequest>,
     chain_states: &State<Arc<Mutex<HashMap<String, ChainState>>>>,
-) -> Result<Json<ApiResponse<[u32; 25]>>, Status> {
+) -> Result<Json<ApiResponse<[u32; 25]>>, BingoError> {
     let chain_states = chain_states.lock().await;
-    println!("Chain states: {:?}", chain_states);
-    let state = chain_states.get(&chain_name).ok_or(Status::NotFound)?;
-    print!("State: {:?}", state);
-    // Parse the wallet address from the request
-    let parsed_address = request.walletAddress.parse::<Address>();
-    let wallet_address = match &parsed_address {
-        Ok(address) => address,
-        Err(_) => {
-            eprintln!("Failed to parse wallet address: {}", request.walletAddress);
-            return Err(Status::BadRequest);
-        }
-    };
-    match state
+    let state = chain_states
+        .get(&chain_name)
+        .ok_or_else(|| BingoError::ChainNotFound(chain_name.clone()))?;
+
+    let wallet_address = request
+        .walletAddress
+        .parse::<Address>()
+        .map_err(|_| BingoError::InvalidAddress(request.walletAddress.clone()))?;
+
+    let cards: [u32; 25] = state
         .app_state
         .contract
-        .get_player_cards(*wallet_address)
+        .get_player_cards(wallet_address)
         .call()
-        .await
-    {
-        Ok(cards) => {
-            let cards: [u32; 25] = cards;
-            println!("cards: {:?}", cards);
-            Ok(Json(ApiResponse {
-                success: true,
-                message: "Get Card".to_string(),
-                data: Some(cards),
-            }))
-        }
-        Err(e) => Ok(Json(ApiResponse {
-            success: false,
-            message: format!("Failed to get player cards: {}", e),
-            data: None,
-        })),
-    }
+        .await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        message: "Get Card".to_string(),
+        data: Some(cards),
+    }))
 }
 
 #[post("/card/challenge/<chain_name>", format = "json", data = "<request>")]
@@ -399,44 +570,190 @@ async fn challenge(
     chain_name: String,
     request: Json<PurchaseCardRequest>,
     chain_states: &State<Arc<Mutex<HashMap<String, ChainState>>>>,
-) -> Result<Json<ApiResponse<bool>>, Status> {
+) -> Result<Json<ApiResponse<ClaimResult>>, BingoError> {
     let chain_states = chain_states.lock().await;
-    let state = chain_states.get(&chain_name).ok_or(Status::NotFound)?;
-    let parsed_address = request.walletAddress.parse::<Address>();
-    let wallet_address = match &parsed_address {
-        Ok(address) => address,
-        Err(_) => {
-            eprintln!("Failed to parse wallet address: {}", request.walletAddress);
-            return Err(Status::BadRequest);
-        }
+    let state = chain_states
+        .get(&chain_name)
+        .ok_or_else(|| BingoError::ChainNotFound(chain_name.clone()))?;
+
+    let wallet_address = request
+        .walletAddress
+        .parse::<Address>()
+        .map_err(|_| BingoError::InvalidAddress(request.walletAddress.clone()))?;
+
+    let call = state.app_state.contract.claim_win(wallet_address);
+    let tx = call.send().await?;
+    let receipt = tx.await?.ok_or(BingoError::TxFailed)?;
+
+    let (receipt, confirmation) = confirm::wait_for_confirmations(
+        state.app_state.contract.client().inner(),
+        receipt,
+        state.confirmations,
+    )
+    .await?;
+    // A claim that's since been reorged out is not a win; resubmit once
+    // before telling the player it's final.
+    let (receipt, confirmation) = if confirmation.canonical {
+        (receipt, confirmation)
+    } else {
+        let call = state.app_state.contract.claim_win(wallet_address);
+        let tx = call.send().await?;
+        let receipt = tx.await?.ok_or(BingoError::TxFailed)?;
+        confirm::wait_for_confirmations(state.app_state.contract.client().inner(), receipt, state.confirmations)
+            .await?
     };
-    match state
-        .app_state
-        .contract
-        .claim_win(*wallet_address)
-        .send()
-        .await
-    {
-        Ok(tx) => match tx.await {
-            Ok(_) => Ok(Json(ApiResponse {
-                success: true,
-                message: "You won!".to_string(),
-                data: Some(true),
-            })),
-            Err(e) => Ok(Json(ApiResponse {
-                success: false,
-                message: format!("Invalid win {}", e),
-                data: Some(false),
-            })),
-        },
-        Err(e) => Ok(Json(ApiResponse {
-            success: false,
-            message: format!("Invalid win {}", e),
-            data: Some(false),
-        })),
+    if confirmation.reverted {
+        return Err(BingoError::TxFailed);
+    }
+
+    state
+        .db
+        .record_claim(
+            &request.walletAddress,
+            &format!("{:?}", receipt.transaction_hash),
+            db::now_unix(),
+        )
+        .await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        message: "You won!".to_string(),
+        data: Some(ClaimResult {
+            won: true,
+            confirmations: confirmation.confirmations,
+            canonical: confirmation.canonical,
+        }),
+    }))
+}
+
+#[get("/game/history/<chain_name>")]
+async fn get_game_history(
+    chain_name: String,
+    chain_states: &State<Arc<Mutex<HashMap<String, ChainState>>>>,
+) -> Result<Json<ApiResponse<db::ChainHistory>>, BingoError> {
+    let chain_states = chain_states.lock().await;
+    let state = chain_states
+        .get(&chain_name)
+        .ok_or_else(|| BingoError::ChainNotFound(chain_name.clone()))?;
+
+    let history = state.db.history().await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        message: format!(
+            "{} draws, {} cards, {} claims",
+            history.draws.len(),
+            history.cards.len(),
+            history.claims.len()
+        ),
+        data: Some(history),
+    }))
+}
+
+#[get("/game/events/<chain_name>")]
+async fn game_events(
+    chain_name: &str,
+    chain_states: &State<Arc<Mutex<HashMap<String, ChainState>>>>,
+) -> Result<EventStream![], Status> {
+    let states = chain_states.lock().await;
+    let state = states.get(chain_name).ok_or(Status::NotFound)?;
+    let rx = state.events_tx.subscribe();
+    drop(states);
+
+    let mut stream = BroadcastStream::new(rx);
+    Ok(EventStream! {
+        while let Some(message) = stream.next().await {
+            match message {
+                Ok(event) => match rocket::serde::json::serde_json::to_string(&event) {
+                    Ok(json) => yield Event::data(json),
+                    Err(e) => eprintln!("Failed to serialize game event: {}", e),
+                },
+                Err(_lagged) => continue,
+            }
+        }
+    })
+}
+
+// The admin API key configured via the `ADMIN_API_KEY` env var, managed as
+// Rocket state so the `AdminAuth` guard can check incoming requests against it
+// without threading it through every admin handler's signature by hand.
+struct AdminApiKey(String);
+
+// Request guard for the `/admin/*` routes: requires an `X-Admin-Api-Key`
+// header matching the configured `AdminApiKey`. These endpoints register and
+// tear down chains — including submitting private keys for fund-moving
+// signers — so they must never be reachable without it.
+struct AdminAuth;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AdminAuth {
+    type Error = BingoError;
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let expected = &request
+            .rocket()
+            .state::<AdminApiKey>()
+            .expect("AdminApiKey must be managed")
+            .0;
+
+        match request.headers().get_one("X-Admin-Api-Key") {
+            Some(provided) if provided == expected => Outcome::Success(AdminAuth),
+            _ => Outcome::Error((Status::Unauthorized, BingoError::Unauthorized)),
+        }
     }
 }
 
+// Rocket catchers don't receive the guard's `Self::Error` value, so this
+// reconstructs the same `BingoError::Unauthorized` the guard above rejected
+// with and responds through its existing `Responder`, keeping a bad/missing
+// admin key on the same uniform `{success, message, data}` JSON body as every
+// other handler failure instead of Rocket's default catcher page.
+#[rocket::catch(401)]
+fn unauthorized_catcher() -> BingoError {
+    BingoError::Unauthorized
+}
+
+#[post("/admin/chains", format = "json", data = "<request>")]
+async fn register_chain_route(
+    _auth: AdminAuth,
+    request: Json<ChainConfig>,
+    chain_states: &State<Arc<Mutex<HashMap<String, ChainState>>>>,
+) -> Result<Json<ApiResponse<()>>, BingoError> {
+    let config = request.into_inner();
+    let chain_name = config.chain_name.clone();
+
+    register_chain(chain_states.inner(), config)
+        .await
+        .map_err(|e| BingoError::ChainInit(e.to_string()))?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        message: format!("chain '{}' registered", chain_name),
+        data: None,
+    }))
+}
+
+#[delete("/admin/chains/<chain_name>")]
+async fn remove_chain(
+    _auth: AdminAuth,
+    chain_name: String,
+    chain_states: &State<Arc<Mutex<HashMap<String, ChainState>>>>,
+) -> Result<Json<ApiResponse<()>>, BingoError> {
+    let mut chain_states = chain_states.lock().await;
+    let state = chain_states
+        .remove(&chain_name)
+        .ok_or_else(|| BingoError::ChainNotFound(chain_name.clone()))?;
+    // Signal the chain's own tasks to stop; they check this flag on their
+    // next loop iteration and exit without disturbing any other chain.
+    state.task_control.store(false, Ordering::SeqCst);
+
+    Ok(Json(ApiResponse {
+        success: true,
+        message: format!("chain '{}' removed", chain_name),
+        data: None,
+    }))
+}
+
 // Helper function to format game status message
 fn format_game_status_message(state: &GameState) -> String {
     if (!state.is_started) {
@@ -451,27 +768,6 @@ fn format_game_status_message(state: &GameState) -> String {
     }
 }
 
-fn extract_card_numbers_from_receipt(receipt: &TransactionReceipt) -> Result<[u32; 25], String> {
-    if let Some(log) = receipt.logs.get(0) {
-        // Extract numbers from log data
-        // This implementation depends on how your contract emits the card numbers
-        // You'll need to adjust this based on your specific contract implementation
-        if log.topics.len() > 1 {
-            let numbers: Vec<u32> = log.topics[1]
-                .as_bytes()
-                .chunks(1)
-                .map(|b| b[0] as u32)
-                .collect();
-            if numbers.len() == 25 {
-                let mut card_numbers = [0u32; 25];
-                card_numbers.copy_from_slice(&numbers);
-                return Ok(card_numbers);
-            }
-        }
-    }
-    Err("Failed to extract card numbers from receipt".to_string())
-}
-
 #[launch]
 async fn rocket() -> _ {
     dotenv().ok();
@@ -481,38 +777,75 @@ async fn rocket() -> _ {
         std::env::var("CONTRACT_ADDRESSES").expect("CONTRACT_ADDRESSES must be set");
     let private_keys = std::env::var("PRIVATE_KEYS").expect("PRIVATE_KEYS must be set");
     let chain_names = std::env::var("CHAIN_NAMES").expect("CHAIN_NAMES must be set");
+    let admin_api_key = std::env::var("ADMIN_API_KEY").expect("ADMIN_API_KEY must be set");
+    // Optional: one websocket endpoint per chain, same order as the other lists.
+    // A chain with no entry (or an empty one) simply gets no live event feed.
+    let ws_urls = std::env::var("WS_URLS").unwrap_or_default();
+    // Optional: one confirmation depth per chain, same order as the other
+    // lists. A chain with no entry (or an empty one) falls back to
+    // DEFAULT_CONFIRMATIONS.
+    let confirmations = std::env::var("CONFIRMATIONS").unwrap_or_default();
 
     let rpc_urls: Vec<&str> = rpc_urls.split(',').collect();
     let contract_addresses: Vec<&str> = contract_addresses.split(',').collect();
     let private_keys: Vec<&str> = private_keys.split(',').collect();
     let chain_names: Vec<&str> = chain_names.split(',').collect();
+    let ws_urls: Vec<&str> = if ws_urls.is_empty() {
+        vec![""; rpc_urls.len()]
+    } else {
+        ws_urls.split(',').collect()
+    };
+    let confirmations: Vec<&str> = if confirmations.is_empty() {
+        vec![""; rpc_urls.len()]
+    } else {
+        confirmations.split(',').collect()
+    };
 
     if rpc_urls.len() != contract_addresses.len()
         || rpc_urls.len() != private_keys.len()
         || rpc_urls.len() != chain_names.len()
     {
-        panic!(
-            "RPC_URLS, CONTRACT_ADDRESSES, PRIVATE_KEYS, and CHAIN_NAMES must have the same length"
-        );
+        panic!("RPC_URLS, CONTRACT_ADDRESSES, PRIVATE_KEYS, and CHAIN_NAMES must have the same length");
     }
-    // Initialize app state for each chain
-    let mut chain_states = HashMap::new();
-    for i in 0..rpc_urls.len() {
-        let chain_state = ChainState::new(rpc_urls[i], contract_addresses[i], private_keys[i])
+
+    // Build a structured config entry per chain rather than indexing five
+    // parallel `Vec`s throughout startup; the optional lists simply fall
+    // back to `None`/defaults when shorter than the required ones.
+    let configs: Vec<ChainConfig> = (0..rpc_urls.len())
+        .map(|i| ChainConfig {
+            chain_name: chain_names[i].to_string(),
+            rpc_url: rpc_urls[i].to_string(),
+            contract_address: contract_addresses[i].to_string(),
+            private_key: private_keys[i].to_string(),
+            ws_url: ws_urls
+                .get(i)
+                .filter(|url| !url.is_empty())
+                .map(|url| url.to_string()),
+            confirmations: confirmations.get(i).and_then(|c| {
+                if c.is_empty() {
+                    None
+                } else {
+                    Some(
+                        c.parse()
+                            .expect("CONFIRMATIONS entries must be non-negative integers"),
+                    )
+                }
+            }),
+        })
+        .collect();
+
+    // Wrap the chain states in an Arc and Mutex for shared access, then
+    // populate it; `register_chain` spawns each one's background tasks itself
+    // as it registers it, so startup chains come up the same way a chain
+    // added later via the admin endpoint does.
+    let chain_states = Arc::new(Mutex::new(HashMap::new()));
+    for config in configs {
+        let chain_name = config.chain_name.clone();
+        register_chain(&chain_states, config)
             .await
-            .expect("Failed to initialize chain state");
-        chain_states.insert(chain_names[i].to_string(), chain_state);
+            .unwrap_or_else(|e| panic!("Failed to initialize chain '{}': {}", chain_name, e));
     }
 
-    // Wrap the chain states in an Arc and Mutex for shared access
-    let chain_states = Arc::new(Mutex::new(chain_states));
-
-    // Create the background submitter
-    let background_submitter = BackgroundSubmitter::new();
-
-    // Get the fairing
-    let fairing = background_submitter.get_fairing();
-
     let cors = CorsOptions::default()
         .allowed_origins(AllowedOrigins::all())
         .allowed_methods(
@@ -526,19 +859,31 @@ async fn rocket() -> _ {
     // Launch Rocket
     rocket::build()
         .manage(chain_states)
+        .manage(AdminApiKey(admin_api_key))
+        .register("/", catchers![unauthorized_catcher])
         .attach(cors.to_cors().unwrap())
         .mount(
             "/api",
-            routes![get_game_state, purchase_card, get_card, challenge],
+            routes![
+                get_game_state,
+                purchase_card,
+                get_card,
+                challenge,
+                game_events,
+                get_game_history,
+                register_chain_route,
+                remove_chain
+            ],
         )
-        .attach(fairing)
 }
 
 // Graceful shutdown handler (add this to your main function if you're not using #[launch])
-pub async fn shutdown_handler(background_submitter: &BackgroundSubmitter) {
+pub async fn shutdown_handler(chain_states: &Arc<Mutex<HashMap<String, ChainState>>>) {
     tokio::signal::ctrl_c()
         .await
         .expect("Failed to listen for ctrl-c");
-    println!("Shutdown signal received, stopping background task...");
-    background_submitter.stop();
+    println!("Shutdown signal received, stopping background tasks...");
+    for state in chain_states.lock().await.values() {
+        state.task_control.store(false, Ordering::SeqCst);
+    }
 }
@@ -0,0 +1,232 @@
+use ethers::prelude::*;
+use ethers::types::{BlockNumber, H256, U256};
+use eyre::Result;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+use crate::db::{self, Store};
+use crate::{BingoGame, ChainSigner};
+
+type SignerContract = BingoGame<ChainSigner>;
+
+// How much to bump EIP-1559 fees on a resend, expressed as a percentage over the
+// previous attempt (numerator/denominator to stay in integer math).
+const FEE_BUMP_NUMERATOR: u64 = 1125;
+const FEE_BUMP_DENOMINATOR: u64 = 1000;
+// Stop re-bumping after this many resends of the same draw, so a truly stuck
+// chain doesn't have the submitter spin forever paying ever-higher fees.
+const MAX_FEE_BUMPS: u8 = 5;
+
+#[derive(Debug)]
+struct PendingTx {
+    number: u8,
+    draw_index: u64,
+    tx_hash: H256,
+    max_fee_per_gas: U256,
+    max_priority_fee_per_gas: U256,
+    submitted_at: Instant,
+    bumps: u8,
+}
+
+// Tracks nonces and in-flight transactions for a chain's signer so draws keep
+// flowing even when the RPC is slow to confirm one. Each draw gets an
+// explicitly assigned nonce up front; a watchdog re-sends anything that hasn't
+// confirmed within `timeout`, reusing the same nonce with bumped gas fees.
+#[derive(Debug)]
+pub struct TxManager {
+    next_nonce: AtomicU64,
+    pending: Mutex<HashMap<u64, PendingTx>>,
+    store: Arc<Store>,
+}
+
+impl TxManager {
+    pub async fn new(contract: &Arc<SignerContract>, store: Arc<Store>) -> Result<Self> {
+        // The nonce always comes from the chain itself rather than the local
+        // store: it's the one source that can't drift out of sync with what
+        // the RPC will actually accept next.
+        let address = contract.client().address();
+        let next_nonce = contract
+            .client()
+            .get_transaction_count(address, Some(BlockNumber::Pending.into()))
+            .await?
+            .as_u64();
+
+        Ok(Self {
+            next_nonce: AtomicU64::new(next_nonce),
+            pending: Mutex::new(HashMap::new()),
+            store,
+        })
+    }
+
+    // Assigns the next nonce, submits `submitDrawnNumber`, and starts tracking
+    // the transaction so the watchdog can bump and re-send it if it stalls.
+    //
+    // The nonce is only consumed once `send_with_nonce` actually succeeds:
+    // estimating fees or broadcasting can fail on an ordinary transient RPC
+    // hiccup, and advancing `next_nonce` regardless would leave that nonce
+    // forever un-broadcast — every later draw then queues behind a gap the
+    // node will never fill, which the watchdog can't rescue since it never
+    // learns the nonce existed. Callers are expected to retry on `Err`, which
+    // will see the same reserved nonce again rather than a fresh one.
+    pub async fn submit_drawn_number(
+        &self,
+        contract: &Arc<SignerContract>,
+        number: u8,
+        draw_index: u64,
+    ) -> Result<()> {
+        let nonce = self.next_nonce.load(Ordering::SeqCst);
+        let (max_fee_per_gas, max_priority_fee_per_gas) =
+            contract.client().estimate_eip1559_fees(None).await?;
+
+        self.send_with_nonce(
+            contract,
+            number,
+            draw_index,
+            nonce,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            0,
+        )
+        .await?;
+        self.next_nonce.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn send_with_nonce(
+        &self,
+        contract: &Arc<SignerContract>,
+        number: u8,
+        draw_index: u64,
+        nonce: u64,
+        max_fee_per_gas: U256,
+        max_priority_fee_per_gas: U256,
+        bumps: u8,
+    ) -> Result<()> {
+        let mut call =
+            contract.submit_drawn_number(U256::from(number), U256::from(draw_index));
+        call.tx.set_nonce(nonce);
+        if let Some(eip1559) = call.tx.as_eip1559_mut() {
+            eip1559.max_fee_per_gas = Some(max_fee_per_gas);
+            eip1559.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+        }
+        let pending_tx = call.send().await?;
+        let tx_hash = pending_tx.tx_hash();
+
+        self.pending.lock().await.insert(
+            nonce,
+            PendingTx {
+                number,
+                draw_index,
+                tx_hash,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                submitted_at: Instant::now(),
+                bumps,
+            },
+        );
+        Ok(())
+    }
+
+    // One watchdog pass: drop anything that has since confirmed, then resend
+    // whatever is still outstanding past `timeout` with bumped EIP-1559 fees,
+    // reusing the same nonce.
+    pub async fn watchdog_tick(&self, contract: &Arc<SignerContract>, timeout: tokio::time::Duration) {
+        self.reap_confirmed(contract).await;
+
+        let stale: Vec<(u64, u8, u64, U256, U256, u8)> = {
+            let pending = self.pending.lock().await;
+            pending
+                .iter()
+                .filter(|(_, tx)| tx.submitted_at.elapsed() > timeout && tx.bumps < MAX_FEE_BUMPS)
+                .map(|(nonce, tx)| {
+                    (
+                        *nonce,
+                        tx.number,
+                        tx.draw_index,
+                        tx.max_fee_per_gas,
+                        tx.max_priority_fee_per_gas,
+                        tx.bumps,
+                    )
+                })
+                .collect()
+        };
+
+        for (nonce, number, draw_index, max_fee_per_gas, max_priority_fee_per_gas, bumps) in stale
+        {
+            let bumped_fee = max_fee_per_gas * FEE_BUMP_NUMERATOR / FEE_BUMP_DENOMINATOR;
+            let bumped_priority_fee =
+                max_priority_fee_per_gas * FEE_BUMP_NUMERATOR / FEE_BUMP_DENOMINATOR;
+            println!(
+                "Resending draw {} (nonce {}, bump #{}) with bumped fees",
+                number,
+                nonce,
+                bumps + 1
+            );
+            if let Err(e) = self
+                .send_with_nonce(
+                    contract,
+                    number,
+                    draw_index,
+                    nonce,
+                    bumped_fee,
+                    bumped_priority_fee,
+                    bumps + 1,
+                )
+                .await
+            {
+                eprintln!("Failed to resend draw {} at nonce {}: {}", number, nonce, e);
+            }
+        }
+    }
+
+    // Drops any tracked transaction whose receipt is already available,
+    // advancing past it without waiting on the draw loop, and records the
+    // confirmed draw for history/resume.
+    async fn reap_confirmed(&self, contract: &Arc<SignerContract>) {
+        let candidates: Vec<(u64, u8, u64, H256)> = {
+            let pending = self.pending.lock().await;
+            pending
+                .iter()
+                .map(|(nonce, tx)| (*nonce, tx.number, tx.draw_index, tx.tx_hash))
+                .collect()
+        };
+
+        for (nonce, number, draw_index, tx_hash) in candidates {
+            match contract.client().get_transaction_receipt(tx_hash).await {
+                Ok(Some(receipt)) => {
+                    self.pending.lock().await.remove(&nonce);
+                    if receipt.status == Some(0.into()) {
+                        // Mined but reverted: the draw never actually advanced
+                        // on-chain, so recording it would corrupt the history
+                        // and resume index with a draw that didn't happen.
+                        eprintln!(
+                            "Draw {} (nonce {}) reverted on-chain; not recording",
+                            draw_index, nonce
+                        );
+                        continue;
+                    }
+                    let block_number = receipt.block_number.map(|b| b.as_u64()).unwrap_or_default();
+                    if let Err(e) = self
+                        .store
+                        .record_draw(
+                            draw_index,
+                            number,
+                            &format!("{:?}", tx_hash),
+                            block_number,
+                            db::now_unix(),
+                        )
+                        .await
+                    {
+                        eprintln!("Failed to persist draw {}: {}", draw_index, e);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => eprintln!("Failed to check receipt for nonce {}: {}", nonce, e),
+            }
+        }
+    }
+}